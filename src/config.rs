@@ -0,0 +1,176 @@
+//! Config-file-driven startup: which multicast groups to subscribe to,
+//! which message kinds to forward, global tags stamped on every event,
+//! the Datadog agent address, and which sinks are enabled. Read from a
+//! TOML or JSON file (selected by extension) so fleets can be customized
+//! per host instead of requiring a recompile.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rtnetlink::sys::constants::*;
+use serde::Deserialize;
+
+use crate::dyndns::DynDnsConfig;
+
+const DEFAULT_CONFIG_PATH: &str = "/etc/navi/config.toml";
+
+/// All message kinds navi knows how to forward. Used both as the default
+/// `forward` list and to validate any list read from the config file.
+const ALL_KINDS: &[&str] =
+    &["link", "address", "route", "neighbour", "qdisc", "class", "filter"];
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default = "default_groups")]
+    pub groups: Vec<String>,
+    #[serde(default = "default_forward")]
+    pub forward: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default = "default_datadog_agent")]
+    pub datadog_agent: String,
+    #[serde(default)]
+    pub sinks: SinkConfig,
+    /// Enables the dynamic-DNS updater when present.
+    #[serde(default)]
+    pub dyndns: Option<DynDnsConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SinkConfig {
+    #[serde(default = "default_true")]
+    pub datadog: bool,
+    pub mqtt: Option<MqttSinkConfig>,
+}
+
+impl Default for SinkConfig {
+    fn default() -> Self {
+        SinkConfig { datadog: true, mqtt: None }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MqttSinkConfig {
+    pub host: String,
+    #[serde(default = "default_mqtt_port")]
+    pub port: u16,
+    #[serde(default = "default_mqtt_qos")]
+    pub qos: u8,
+}
+
+fn default_groups() -> Vec<String> {
+    ALL_KINDS_AND_GROUPS.iter().map(|s| s.to_string()).collect()
+}
+
+const ALL_KINDS_AND_GROUPS: &[&str] = &[
+    "link",
+    "ipv4-ifaddr",
+    "ipv6-ifaddr",
+    "ipv4-route",
+    "ipv6-route",
+    "mpls-route",
+    "ipv4-mroute",
+    "ipv6-mroute",
+    "neigh",
+    "ipv4-netconf",
+    "ipv6-netconf",
+    "ipv4-rule",
+    "ipv6-rule",
+    "nsid",
+    "mpls-netconf",
+    "tc",
+];
+
+fn default_forward() -> Vec<String> {
+    ALL_KINDS.iter().map(|s| s.to_string()).collect()
+}
+
+fn default_datadog_agent() -> String {
+    "127.0.0.1:8125".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_mqtt_port() -> u16 {
+    1883
+}
+
+fn default_mqtt_qos() -> u8 {
+    1
+}
+
+impl Config {
+    /// Loads the config from `path`, or `DEFAULT_CONFIG_PATH` if none is
+    /// given. The format (TOML or JSON) is selected by file extension.
+    pub fn load(path: Option<&Path>) -> Result<Self, String> {
+        let path = path.map(PathBuf::from).unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_PATH));
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| format!("failed to read config file {}: {}", path.display(), e))?;
+
+        let config: Config = if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+            serde_json::from_str(&contents)
+                .map_err(|e| format!("invalid JSON config {}: {}", path.display(), e))?
+        } else {
+            toml::from_str(&contents)
+                .map_err(|e| format!("invalid TOML config {}: {}", path.display(), e))?
+        };
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        for group in &self.groups {
+            if group_mask(group).is_none() {
+                return Err(format!("unknown multicast group {:?}", group));
+            }
+        }
+        for kind in &self.forward {
+            if !ALL_KINDS.contains(&kind.as_str()) {
+                return Err(format!("unknown message kind {:?}", kind));
+            }
+        }
+        if !self.sinks.datadog && self.sinks.mqtt.is_none() {
+            return Err("at least one sink must be enabled".to_string());
+        }
+        Ok(())
+    }
+
+    /// Builds the multicast group mask to bind the netlink socket with.
+    pub fn group_mask(&self) -> u32 {
+        self.groups.iter().filter_map(|g| group_mask(g)).fold(0, |mask, group| mask | group)
+    }
+
+    /// Whether events of `kind` (e.g. "link", "address") should be
+    /// forwarded to the configured sinks.
+    pub fn forwards(&self, kind: &str) -> bool {
+        self.forward.iter().any(|k| k == kind)
+    }
+}
+
+fn group_mask(name: &str) -> Option<u32> {
+    Some(match name {
+        "link" => RTNLGRP_LINK,
+        "ipv4-ifaddr" => RTNLGRP_IPV4_IFADDR,
+        "ipv6-ifaddr" => RTNLGRP_IPV6_IFADDR,
+        "ipv4-route" => RTNLGRP_IPV4_ROUTE,
+        "ipv6-route" => RTNLGRP_IPV6_ROUTE,
+        "mpls-route" => RTNLGRP_MPLS_ROUTE,
+        "ipv4-mroute" => RTNLGRP_IPV4_MROUTE,
+        "ipv6-mroute" => RTNLGRP_IPV6_MROUTE,
+        "neigh" => RTNLGRP_NEIGH,
+        "ipv4-netconf" => RTNLGRP_IPV4_NETCONF,
+        "ipv6-netconf" => RTNLGRP_IPV6_NETCONF,
+        "ipv4-rule" => RTNLGRP_IPV4_RULE,
+        "ipv6-rule" => RTNLGRP_IPV6_RULE,
+        "nsid" => RTNLGRP_NSID,
+        "mpls-netconf" => RTNLGRP_MPLS_NETCONF,
+        "tc" => RTNLGRP_TC,
+        _ => return None,
+    })
+}
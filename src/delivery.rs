@@ -0,0 +1,135 @@
+//! Reliable event delivery. The netlink loop pushes built events onto a
+//! bounded queue instead of sending to each sink inline, so a transient
+//! outage can no longer panic the whole agent. A dedicated sender task
+//! drains the queue, fanning each event out to every configured sink with
+//! retrying, backed-off sends, and coalesces duplicate events so a
+//! flapping interface doesn't spam.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::sync::mpsc;
+use tokio::time::sleep;
+
+use crate::shutdown::Shutdown;
+use crate::sink::{EventSink, NetworkEvent};
+
+const QUEUE_CAPACITY: usize = 1024;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+const MAX_BACKOFF: Duration = Duration::from_secs(10);
+const MAX_ATTEMPTS: u32 = 5;
+const COALESCE_WINDOW: Duration = Duration::from_secs(5);
+const SHUTDOWN_FLUSH_DEADLINE: Duration = Duration::from_secs(5);
+
+#[derive(Clone)]
+pub struct EventQueue {
+    tx: mpsc::Sender<NetworkEvent>,
+    global_tags: Arc<Vec<String>>,
+}
+
+impl EventQueue {
+    pub async fn push(&self, mut event: NetworkEvent) {
+        event.tags.extend(self.global_tags.iter().cloned());
+        if self.tx.send(event).await.is_err() {
+            eprintln!("Delivery: queue closed, dropping event");
+        }
+    }
+}
+
+/// Creates the queue/receiver pair. `global_tags` (from config) are
+/// stamped onto every event pushed through the returned `EventQueue`.
+pub fn channel(global_tags: Vec<String>) -> (EventQueue, mpsc::Receiver<NetworkEvent>) {
+    let (tx, rx) = mpsc::channel(QUEUE_CAPACITY);
+    (EventQueue { tx, global_tags: Arc::new(global_tags) }, rx)
+}
+
+/// Drains `rx`, fanning each event out to `sinks` with exponential backoff
+/// retry per sink, and coalescing duplicates of the last delivered event.
+/// Once `shutdown` fires, stops waiting for new events and flushes
+/// whatever is left in the queue within a deadline.
+pub async fn run_sender(
+    sinks: Vec<Arc<dyn EventSink>>,
+    mut rx: mpsc::Receiver<NetworkEvent>,
+    shutdown: Shutdown,
+) {
+    let mut last_sent: Option<(String, String, Instant)> = None;
+
+    loop {
+        let event = tokio::select! {
+            event = rx.recv() => match event {
+                Some(event) => event,
+                None => break,
+            },
+            _ = shutdown.notified() => break,
+        };
+
+        if is_duplicate(&last_sent, &event) {
+            println!("Delivery: coalescing duplicate event {:?}", event.title);
+            continue;
+        }
+
+        dispatch(&sinks, &event).await;
+        last_sent = Some((event.title.clone(), event.text.clone(), Instant::now()));
+    }
+
+    flush_remaining(&sinks, &mut rx, &mut last_sent).await;
+}
+
+fn is_duplicate(last_sent: &Option<(String, String, Instant)>, event: &NetworkEvent) -> bool {
+    match last_sent {
+        Some((title, text, at)) => {
+            *title == event.title && *text == event.text && at.elapsed() < COALESCE_WINDOW
+        }
+        None => false,
+    }
+}
+
+async fn dispatch(sinks: &[Arc<dyn EventSink>], event: &NetworkEvent) {
+    for sink in sinks {
+        send_with_retry(sink.as_ref(), event).await;
+    }
+}
+
+async fn send_with_retry(sink: &dyn EventSink, event: &NetworkEvent) -> bool {
+    let mut backoff = INITIAL_BACKOFF;
+    for attempt in 1..=MAX_ATTEMPTS {
+        match sink.emit(event).await {
+            Ok(()) => return true,
+            Err(e) => {
+                eprintln!(
+                    "Delivery: attempt {}/{} failed to send {:?}: {}",
+                    attempt, MAX_ATTEMPTS, event.title, e
+                );
+                if attempt == MAX_ATTEMPTS {
+                    break;
+                }
+                sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+        }
+    }
+    false
+}
+
+/// Best-effort flush of whatever is still queued at shutdown, bounded by
+/// `SHUTDOWN_FLUSH_DEADLINE` so an unreachable sink can't hang process
+/// exit.
+async fn flush_remaining(
+    sinks: &[Arc<dyn EventSink>],
+    rx: &mut mpsc::Receiver<NetworkEvent>,
+    last_sent: &mut Option<(String, String, Instant)>,
+) {
+    rx.close();
+    let deadline = Instant::now() + SHUTDOWN_FLUSH_DEADLINE;
+    while Instant::now() < deadline {
+        let event = match rx.try_recv() {
+            Ok(event) => event,
+            Err(_) => break,
+        };
+        if is_duplicate(last_sent, &event) {
+            continue;
+        }
+        dispatch(sinks, &event).await;
+        *last_sent = Some((event.title.clone(), event.text.clone(), Instant::now()));
+    }
+}
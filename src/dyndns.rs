@@ -0,0 +1,142 @@
+//! Optional dynamic-DNS backend: keeps an external DNS record in sync with
+//! the host's public IPv4 address by reacting to `NewAddress`/`DelAddress`
+//! events, the way a dyndns agent does, instead of polling. Enabled by
+//! adding a `[dyndns]` section to the config file; absent that section the
+//! subsystem is disabled entirely.
+
+use std::net::Ipv4Addr;
+use std::time::{Duration, Instant};
+
+use rtnetlink::packet::{rtnl::address::nlas::Nla as AddressNla, AddressMessage};
+use serde::Deserialize;
+
+const RT_SCOPE_UNIVERSE: u8 = 0;
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DynDnsConfig {
+    pub interface_index: u32,
+    pub endpoint: String,
+    pub token: String,
+    pub record_id: String,
+    #[serde(default = "default_debounce_secs")]
+    pub debounce_secs: u64,
+}
+
+impl DynDnsConfig {
+    fn debounce(&self) -> Duration {
+        Duration::from_secs(self.debounce_secs)
+    }
+}
+
+fn default_debounce_secs() -> u64 {
+    60
+}
+
+pub struct DynDnsUpdater {
+    config: DynDnsConfig,
+    http: reqwest::Client,
+    last_known_ipv4: Option<Ipv4Addr>,
+    last_update: Option<Instant>,
+}
+
+impl DynDnsUpdater {
+    pub fn new(config: DynDnsConfig) -> Self {
+        DynDnsUpdater {
+            config,
+            http: reqwest::Client::new(),
+            last_known_ipv4: None,
+            last_update: None,
+        }
+    }
+
+    /// Inspects a `NewAddress` event and, if it reports a new routable
+    /// IPv4 on the monitored interface, pushes a DNS record update.
+    pub async fn on_address_added(&mut self, am: &AddressMessage) {
+        if am.header.index != self.config.interface_index {
+            return;
+        }
+        if am.header.scope != RT_SCOPE_UNIVERSE {
+            return;
+        }
+        let addr = match find_ipv4(am) {
+            Some(addr) => addr,
+            None => return,
+        };
+        if !is_routable(addr) {
+            return;
+        }
+        if Some(addr) == self.last_known_ipv4 {
+            return;
+        }
+        if self.is_debounced() {
+            println!("Dyndns: ignoring {} update, still within debounce window", addr);
+            return;
+        }
+
+        match self.push_update(addr).await {
+            Ok(()) => {
+                self.last_known_ipv4 = Some(addr);
+                self.last_update = Some(Instant::now());
+            }
+            Err(e) => eprintln!("Dyndns: failed to update record {}: {}", self.config.record_id, e),
+        }
+    }
+
+    /// Inspects a `DelAddress` event and, if it reports the loss of the
+    /// address we last pushed, clears our cached address so the next
+    /// `NewAddress` triggers an update even if the same address reappears.
+    pub async fn on_address_removed(&mut self, am: &AddressMessage) {
+        if am.header.index != self.config.interface_index {
+            return;
+        }
+        let addr = match find_ipv4(am) {
+            Some(addr) => addr,
+            None => return,
+        };
+        if Some(addr) == self.last_known_ipv4 {
+            println!("Dyndns: tracked address {} removed from index {}", addr, am.header.index);
+            self.last_known_ipv4 = None;
+        }
+    }
+
+    fn is_debounced(&self) -> bool {
+        match self.last_update {
+            Some(at) => at.elapsed() < self.config.debounce(),
+            None => false,
+        }
+    }
+
+    async fn push_update(&self, addr: Ipv4Addr) -> Result<(), String> {
+        let url = format!("{}/{}", self.config.endpoint, self.config.record_id);
+        self.http
+            .patch(&url)
+            .bearer_auth(&self.config.token)
+            .json(&serde_json::json!({ "type": "A", "content": addr.to_string() }))
+            .send()
+            .await
+            .map_err(|e| format!("{}", e))?
+            .error_for_status()
+            .map_err(|e| format!("{}", e))?;
+        println!("Dyndns: updated record {} to {}", self.config.record_id, addr);
+        Ok(())
+    }
+}
+
+fn find_ipv4(am: &AddressMessage) -> Option<Ipv4Addr> {
+    for nla in am.nlas.iter() {
+        match nla {
+            AddressNla::Address(bytes) | AddressNla::Local(bytes) if bytes.len() == 4 => {
+                let mut octets = [0u8; 4];
+                octets.copy_from_slice(bytes);
+                return Some(Ipv4Addr::from(octets));
+            }
+            _ => continue,
+        }
+    }
+    None
+}
+
+fn is_routable(addr: Ipv4Addr) -> bool {
+    !addr.is_loopback() && !addr.is_link_local()
+}
@@ -1,44 +1,54 @@
-use futures::stream::StreamExt;
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::Arc;
+
+use futures::stream::{StreamExt, TryStreamExt};
 
 use rtnetlink::{
     new_connection,
-    packet::{rtnl::link::nlas::Nla, LinkMessage, NetlinkPayload, RtnlMessage, RtnlMessage::*},
+    packet::{
+        rtnl::{
+            address::nlas::Nla as AddressNla, link::nlas::Nla, neighbour::nlas::Nla as NeighbourNla,
+            route::nlas::Nla as RouteNla, tc::nlas::Nla as TcNla,
+        },
+        AddressMessage, LinkMessage, NeighbourMessage, NetlinkMessage, NetlinkPayload, RouteMessage,
+        RtnlMessage, RtnlMessage::*, TcMessage, NLM_F_DUMP, NLM_F_REQUEST,
+    },
     sys::{constants::*, SocketAddr},
+    Handle,
 };
 
-use zoomies::{Client, ConfigBuilder, Event};
+use zoomies::{Client, ConfigBuilder};
+
+mod config;
+mod delivery;
+mod dyndns;
+mod shutdown;
+mod sink;
+
+use config::Config;
+use delivery::EventQueue;
+use dyndns::DynDnsUpdater;
+use shutdown::Shutdown;
+use sink::{DatadogSink, EventSink, MqttConfig, MqttSink, NetworkEvent};
 
 #[tokio::main(max_threads = 1, core_threads = 1)]
 async fn main() -> Result<(), String> {
+    // An explicit path can be passed as the first CLI argument; otherwise
+    // we fall back to the default config location.
+    let config_path = std::env::args().nth(1);
+    let config = Config::load(config_path.as_deref().map(std::path::Path::new))?;
+
     // conn - `Connection` that has a netlink socket which is a `Future` that polls the socket
     // and thus must have an event loop
     //
     // handle - `Handle` to the `Connection`. Used to send/recv netlink messages.
     //
     // messages - A channel receiver.
-    let (mut conn, mut _handle, mut messages) = new_connection().map_err(|e| format!("{}", e))?;
-
-    // Create a datadog client.
-    let dd = Client::with_config(ConfigBuilder::new().finish())
-        .await
-        .map_err(|e| format!("{}", e))?;
+    let (mut conn, mut handle, mut messages) = new_connection().map_err(|e| format!("{}", e))?;
 
     // These flags specify what kinds of broadcast messages we want to listen for.
-    let groups = RTNLGRP_LINK
-        | RTNLGRP_IPV4_IFADDR
-        | RTNLGRP_IPV6_IFADDR
-        | RTNLGRP_IPV4_ROUTE
-        | RTNLGRP_IPV6_ROUTE
-        | RTNLGRP_MPLS_ROUTE
-        | RTNLGRP_IPV4_MROUTE
-        | RTNLGRP_IPV6_MROUTE
-        | RTNLGRP_NEIGH
-        | RTNLGRP_IPV4_NETCONF
-        | RTNLGRP_IPV6_NETCONF
-        | RTNLGRP_IPV4_RULE
-        | RTNLGRP_IPV6_RULE
-        | RTNLGRP_NSID
-        | RTNLGRP_MPLS_NETCONF;
+    let groups = config.group_mask();
 
     // Create new socket that listens for the messages described above.
     let addr = SocketAddr::new(0, groups);
@@ -47,26 +57,128 @@ async fn main() -> Result<(), String> {
     // Spawn `Connection` to start polling netlink socket.
     tokio::spawn(conn);
 
-    // Start receiving events through `messages` channel.
-    while let Some((message, _)) = messages.next().await {
-        match message.payload {
-            NetlinkPayload::Done => {
-                println!("Done");
-            }
-            NetlinkPayload::Error(em) => {
-                eprintln!("Error: {:?}", em);
+    // Snapshot the qdiscs that already exist before we start reacting to
+    // broadcast changes, so the first change we see has a known baseline.
+    snapshot_qdiscs(&mut handle).await;
+
+    // Snapshot ifindex->name for every existing link, so address/route/
+    // neighbour events (which only carry an index) can tag the interface by
+    // name. Kept up to date afterwards from link events as they arrive.
+    let mut ifaces = snapshot_ifaces(&mut handle).await;
+
+    // The dyndns subsystem is optional: it only runs when the config file
+    // has a `[dyndns]` section.
+    let mut dyndns = config.dyndns.clone().map(DynDnsUpdater::new);
+
+    // Build the configured sinks.
+    let mut sinks: Vec<Arc<dyn EventSink>> = Vec::new();
+    if config.sinks.datadog {
+        let dd = Client::with_config(ConfigBuilder::new().agent_addr(config.datadog_agent.clone()).finish())
+            .await
+            .map_err(|e| format!("{}", e))?;
+        sinks.push(Arc::new(DatadogSink::new(dd)));
+    }
+    if let Some(mqtt_config) = &config.sinks.mqtt {
+        let mqtt_config = MqttConfig::new(mqtt_config.host.clone(), mqtt_config.port, mqtt_config.qos);
+        match MqttSink::connect(mqtt_config).await {
+            Ok(mqtt) => sinks.push(Arc::new(mqtt)),
+            Err(e) => eprintln!("Mqtt: failed to connect, sink disabled: {}", e),
+        }
+    }
+
+    // Delivery is decoupled from the netlink loop: handlers push built
+    // events onto a bounded queue and a dedicated task drains it, fanning
+    // out to every sink with retries, so an outage can't panic the agent.
+    // Reading the next netlink message only waits while the queue is full.
+    let shutdown = Shutdown::install();
+    let (queue, rx) = delivery::channel(config.tags.clone());
+    let sender = tokio::spawn(delivery::run_sender(sinks, rx, shutdown.clone()));
+
+    // Start receiving events through `messages` channel, until shutdown is
+    // requested.
+    loop {
+        tokio::select! {
+            _ = shutdown.notified() => {
+                println!("Stopping netlink listener");
+                break;
             }
-            NetlinkPayload::Ack(_am) => {}
-            NetlinkPayload::Noop => {}
-            NetlinkPayload::Overrun(_bytes) => {}
-            NetlinkPayload::InnerMessage(m) => {
-                handle_message(&dd, m).await;
+            next = messages.next() => {
+                match next {
+                    Some((message, _)) => match message.payload {
+                        NetlinkPayload::Done => {
+                            println!("Done");
+                        }
+                        NetlinkPayload::Error(em) => {
+                            eprintln!("Error: {:?}", em);
+                        }
+                        NetlinkPayload::Ack(_am) => {}
+                        NetlinkPayload::Noop => {}
+                        NetlinkPayload::Overrun(_bytes) => {}
+                        NetlinkPayload::InnerMessage(m) => {
+                            handle_message(&queue, &config, &mut ifaces, m, dyndns.as_mut()).await;
+                        }
+                    },
+                    None => break,
+                }
             }
         }
     }
+
+    // Let the sender drain remaining queued events before we exit.
+    drop(queue);
+    let _ = sender.await;
     Ok(())
 }
 
+/// Tracks the interface name for each known ifindex, so handlers that only
+/// see an index (address, route, neighbour events) can tag the interface by
+/// name instead. Seeded from a startup dump and kept current from link
+/// events as they arrive.
+struct IfaceCache(HashMap<u32, String>);
+
+impl IfaceCache {
+    fn insert(&mut self, index: u32, name: String) {
+        self.0.insert(index, name);
+    }
+
+    fn remove(&mut self, index: u32) {
+        self.0.remove(&index);
+    }
+
+    /// Resolves `index` to its interface name, falling back to the bare
+    /// index if the link hasn't been seen yet.
+    fn name_or_index(&self, index: u32) -> String {
+        self.0.get(&index).cloned().unwrap_or_else(|| index.to_string())
+    }
+}
+
+// Issues a `GetLink` dump request and drains the replies to build the
+// initial ifindex->name mapping before we start reacting to broadcast
+// changes.
+async fn snapshot_ifaces(handle: &mut Handle) -> IfaceCache {
+    let mut cache = HashMap::new();
+    let mut req = NetlinkMessage::from(RtnlMessage::GetLink(LinkMessage::default()));
+    req.header.flags = NLM_F_REQUEST | NLM_F_DUMP;
+
+    let mut response = match handle.request(req) {
+        Ok(response) => response,
+        Err(e) => {
+            eprintln!("Failed to request link dump: {}", e);
+            return IfaceCache(cache);
+        }
+    };
+
+    while let Ok(Some(message)) = response.try_next().await {
+        if let NetlinkPayload::InnerMessage(RtnlMessage::NewLink(lm)) = message.payload {
+            let index = lm.header.index as u32;
+            if let Some(name) = find_ifname(lm) {
+                cache.insert(index, name);
+            }
+        }
+    }
+    IfaceCache(cache)
+}
+
 fn find_ifname(lm: LinkMessage) -> Option<String> {
     for nla in lm.nlas.into_iter() {
         match nla {
@@ -77,62 +189,399 @@ fn find_ifname(lm: LinkMessage) -> Option<String> {
     None
 }
 
-async fn on_link_deleted(dd: &Client, lm: LinkMessage) {
+// Netlink addresses are carried as raw bytes whose length tells us the
+// family: 4 bytes for IPv4, 16 for IPv6.
+fn parse_ip(bytes: &[u8]) -> Option<IpAddr> {
+    match bytes.len() {
+        4 => {
+            let mut octets = [0u8; 4];
+            octets.copy_from_slice(bytes);
+            Some(IpAddr::V4(Ipv4Addr::from(octets)))
+        }
+        16 => {
+            let mut octets = [0u8; 16];
+            octets.copy_from_slice(bytes);
+            Some(IpAddr::V6(Ipv6Addr::from(octets)))
+        }
+        _ => None,
+    }
+}
+
+fn scope_name(scope: u8) -> &'static str {
+    // See RT_SCOPE_* in rtnetlink(7).
+    match scope {
+        0 => "universe",
+        200 => "site",
+        253 => "link",
+        254 => "host",
+        255 => "nowhere",
+        _ => "unknown",
+    }
+}
+
+struct AddressInfo {
+    address: Option<IpAddr>,
+    prefix_len: u8,
+    scope: &'static str,
+    index: u32,
+}
+
+fn find_address_info(am: &AddressMessage) -> AddressInfo {
+    let mut address = None;
+    let mut local = None;
+    for nla in am.nlas.iter() {
+        match nla {
+            // Prefer `Address`, but fall back to `Local` for interfaces (e.g.
+            // point-to-point links) that only carry the latter.
+            AddressNla::Address(bytes) => address = parse_ip(bytes),
+            AddressNla::Local(bytes) => local = parse_ip(bytes),
+            _ => continue,
+        }
+    }
+    AddressInfo {
+        address: address.or(local),
+        prefix_len: am.header.prefix_len,
+        scope: scope_name(am.header.scope),
+        index: am.header.index,
+    }
+}
+
+struct RouteInfo {
+    destination: Option<IpAddr>,
+    destination_prefix_len: u8,
+    gateway: Option<IpAddr>,
+    oif_index: Option<u32>,
+}
+
+fn find_route_info(rm: &RouteMessage) -> RouteInfo {
+    let mut destination = None;
+    let mut gateway = None;
+    let mut oif_index = None;
+    for nla in rm.nlas.iter() {
+        match nla {
+            RouteNla::Destination(bytes) => destination = parse_ip(bytes),
+            RouteNla::Gateway(bytes) => gateway = parse_ip(bytes),
+            RouteNla::Oif(index) => oif_index = Some(*index),
+            _ => continue,
+        }
+    }
+    RouteInfo {
+        destination,
+        destination_prefix_len: rm.header.destination_prefix_length,
+        gateway,
+        oif_index,
+    }
+}
+
+fn find_neighbour_address(nm: &NeighbourMessage) -> Option<IpAddr> {
+    for nla in nm.nlas.iter() {
+        match nla {
+            NeighbourNla::Destination(bytes) => return parse_ip(bytes),
+            _ => continue,
+        }
+    }
+    None
+}
+
+// `TcHandle`s pack a major and minor number into a single u32: the major
+// number in the high 16 bits, the minor number in the low 16 bits.
+fn format_tc_handle(handle: u32) -> String {
+    format!("{}:{}", handle >> 16, handle & 0xffff)
+}
+
+fn find_tc_kind(tm: &TcMessage) -> Option<String> {
+    for nla in tm.nlas.iter() {
+        match nla {
+            TcNla::Kind(kind) => return Some(kind.clone()),
+            _ => continue,
+        }
+    }
+    None
+}
+
+fn describe_tc(tm: &TcMessage) -> String {
+    let kind = find_tc_kind(tm).unwrap_or_else(|| "unknown".to_string());
+    format!(
+        "{} handle {} on index {}",
+        kind,
+        format_tc_handle(tm.header.handle),
+        tm.header.index
+    )
+}
+
+async fn on_link_deleted(queue: &EventQueue, lm: LinkMessage, ifaces: &mut IfaceCache) {
     println!("Interface Deleted");
+    let index = lm.header.index as u32;
     if let Some(name) = find_ifname(lm) {
         println!("{:?} was deleted", name);
-        let event = Event::new().title("Interface Deleted").text(&name).build().expect("nice");
-        dd.send(&event).await.expect("failed");
+        let event = NetworkEvent::new("Interface Deleted", name.as_str(), "link", "deleted", name.as_str());
+        queue.push(event).await;
     }
+    ifaces.remove(index);
 }
 
-async fn on_link_created(dd: &Client, lm: LinkMessage) {
+async fn on_link_created(queue: &EventQueue, lm: LinkMessage, ifaces: &mut IfaceCache) {
+    let index = lm.header.index as u32;
     if let Some(name) = find_ifname(lm) {
         println!("Interface {} is up", name);
-        let event = Event::new().title("Interface Created").text(&name).build().expect("nice");
-        dd.send(&event).await.expect("failed");
+        let event = NetworkEvent::new("Interface Created", name.as_str(), "link", "created", name.as_str());
+        queue.push(event).await;
+        ifaces.insert(index, name);
     }
 }
 
-async fn on_link_set(dd: &Client, lm: LinkMessage) {
+async fn on_link_set(queue: &EventQueue, lm: LinkMessage, ifaces: &mut IfaceCache) {
+    let index = lm.header.index as u32;
     if let Some(name) = find_ifname(lm) {
         println!("Interface {:?} was set.", name);
-        let event = Event::new().title("Interface Set").text(&name).build().expect("nice");
-        dd.send(&event).await.expect("failed");
+        let event = NetworkEvent::new("Interface Set", name.as_str(), "link", "set", name.as_str());
+        queue.push(event).await;
+        ifaces.insert(index, name);
+    }
+}
+
+async fn on_address_created(queue: &EventQueue, am: AddressMessage, ifaces: &IfaceCache) {
+    let info = find_address_info(&am);
+    if let Some(addr) = info.address {
+        println!("Address {}/{} added on index {}", addr, info.prefix_len, info.index);
+        let text = format!("{}/{} (scope: {})", addr, info.prefix_len, info.scope);
+        let event = NetworkEvent::new("Address Added", text, "address", "created", addr.to_string())
+            .with_tags(vec![
+                format!("interface:{}", ifaces.name_or_index(info.index)),
+                format!("family:{}", if addr.is_ipv4() { "ipv4" } else { "ipv6" }),
+                format!("scope:{}", info.scope),
+            ]);
+        queue.push(event).await;
+    }
+}
+
+async fn on_address_deleted(queue: &EventQueue, am: AddressMessage, ifaces: &IfaceCache) {
+    let info = find_address_info(&am);
+    if let Some(addr) = info.address {
+        println!("Address {}/{} removed from index {}", addr, info.prefix_len, info.index);
+        let text = format!("{}/{} (scope: {})", addr, info.prefix_len, info.scope);
+        let event = NetworkEvent::new("Address Removed", text, "address", "deleted", addr.to_string())
+            .with_tags(vec![
+                format!("interface:{}", ifaces.name_or_index(info.index)),
+                format!("family:{}", if addr.is_ipv4() { "ipv4" } else { "ipv6" }),
+                format!("scope:{}", info.scope),
+            ]);
+        queue.push(event).await;
+    }
+}
+
+async fn on_route_created(queue: &EventQueue, rm: RouteMessage, ifaces: &IfaceCache) {
+    let info = find_route_info(&rm);
+    let dest = info
+        .destination
+        .map(|d| format!("{}/{}", d, info.destination_prefix_len))
+        .unwrap_or_else(|| "default".to_string());
+    println!("Route added: {}", dest);
+    let mut tags = Vec::new();
+    if let Some(gw) = info.gateway {
+        tags.push(format!("gateway:{}", gw));
+    }
+    if let Some(oif) = info.oif_index {
+        tags.push(format!("interface:{}", ifaces.name_or_index(oif)));
+    }
+    let event =
+        NetworkEvent::new("Route Added", dest.clone(), "route", "created", dest).with_tags(tags);
+    queue.push(event).await;
+}
+
+async fn on_route_deleted(queue: &EventQueue, rm: RouteMessage, ifaces: &IfaceCache) {
+    let info = find_route_info(&rm);
+    let dest = info
+        .destination
+        .map(|d| format!("{}/{}", d, info.destination_prefix_len))
+        .unwrap_or_else(|| "default".to_string());
+    println!("Route removed: {}", dest);
+    let mut tags = Vec::new();
+    if let Some(gw) = info.gateway {
+        tags.push(format!("gateway:{}", gw));
+    }
+    if let Some(oif) = info.oif_index {
+        tags.push(format!("interface:{}", ifaces.name_or_index(oif)));
+    }
+    let event =
+        NetworkEvent::new("Route Removed", dest.clone(), "route", "deleted", dest).with_tags(tags);
+    queue.push(event).await;
+}
+
+async fn on_neighbour_created(queue: &EventQueue, nm: NeighbourMessage, ifaces: &IfaceCache) {
+    if let Some(addr) = find_neighbour_address(&nm) {
+        println!("Neighbour {} appeared on index {}", addr, nm.header.ifindex);
+        let event = NetworkEvent::new(
+            "Neighbour Added",
+            addr.to_string(),
+            "neighbour",
+            "created",
+            addr.to_string(),
+        )
+        .with_tags(vec![format!("interface:{}", ifaces.name_or_index(nm.header.ifindex as u32))]);
+        queue.push(event).await;
+    }
+}
+
+async fn on_neighbour_deleted(queue: &EventQueue, nm: NeighbourMessage, ifaces: &IfaceCache) {
+    if let Some(addr) = find_neighbour_address(&nm) {
+        println!("Neighbour {} removed from index {}", addr, nm.header.ifindex);
+        let event = NetworkEvent::new(
+            "Neighbour Removed",
+            addr.to_string(),
+            "neighbour",
+            "deleted",
+            addr.to_string(),
+        )
+        .with_tags(vec![format!("interface:{}", ifaces.name_or_index(nm.header.ifindex as u32))]);
+        queue.push(event).await;
     }
 }
 
-async fn handle_message(dd: &Client, msg: RtnlMessage) {
+async fn on_qdisc_created(queue: &EventQueue, tm: TcMessage) {
+    let desc = describe_tc(&tm);
+    println!("Qdisc added: {}", desc);
+    let handle = format_tc_handle(tm.header.handle);
+    let event = NetworkEvent::new("Qdisc Added", desc, "qdisc", "created", handle)
+        .with_tags(vec![format!("interface_index:{}", tm.header.index)]);
+    queue.push(event).await;
+}
+
+async fn on_qdisc_deleted(queue: &EventQueue, tm: TcMessage) {
+    let desc = describe_tc(&tm);
+    println!("Qdisc removed: {}", desc);
+    let handle = format_tc_handle(tm.header.handle);
+    let event = NetworkEvent::new("Qdisc Removed", desc, "qdisc", "deleted", handle)
+        .with_tags(vec![format!("interface_index:{}", tm.header.index)]);
+    queue.push(event).await;
+}
+
+async fn on_class_created(queue: &EventQueue, tm: TcMessage) {
+    let desc = describe_tc(&tm);
+    println!("Traffic class added: {}", desc);
+    let handle = format_tc_handle(tm.header.handle);
+    let event = NetworkEvent::new("Traffic Class Added", desc, "class", "created", handle)
+        .with_tags(vec![format!("interface_index:{}", tm.header.index)]);
+    queue.push(event).await;
+}
+
+async fn on_class_deleted(queue: &EventQueue, tm: TcMessage) {
+    let desc = describe_tc(&tm);
+    println!("Traffic class removed: {}", desc);
+    let handle = format_tc_handle(tm.header.handle);
+    let event = NetworkEvent::new("Traffic Class Removed", desc, "class", "deleted", handle)
+        .with_tags(vec![format!("interface_index:{}", tm.header.index)]);
+    queue.push(event).await;
+}
+
+async fn on_filter_created(queue: &EventQueue, tm: TcMessage) {
+    let desc = describe_tc(&tm);
+    println!("Traffic filter added: {}", desc);
+    let handle = format_tc_handle(tm.header.handle);
+    let event = NetworkEvent::new("Traffic Filter Added", desc, "filter", "created", handle)
+        .with_tags(vec![format!("interface_index:{}", tm.header.index)]);
+    queue.push(event).await;
+}
+
+async fn on_filter_deleted(queue: &EventQueue, tm: TcMessage) {
+    let desc = describe_tc(&tm);
+    println!("Traffic filter removed: {}", desc);
+    let handle = format_tc_handle(tm.header.handle);
+    let event = NetworkEvent::new("Traffic Filter Removed", desc, "filter", "deleted", handle)
+        .with_tags(vec![format!("interface_index:{}", tm.header.index)]);
+    queue.push(event).await;
+}
+
+// Issues a `GetQueueDiscipline` dump request and drains the replies so the
+// current qdisc configuration is known before we start reacting to
+// broadcast changes.
+async fn snapshot_qdiscs(handle: &mut Handle) {
+    let mut req = NetlinkMessage::from(RtnlMessage::GetQueueDiscipline(TcMessage::default()));
+    req.header.flags = NLM_F_REQUEST | NLM_F_DUMP;
+
+    let mut response = match handle.request(req) {
+        Ok(response) => response,
+        Err(e) => {
+            eprintln!("Failed to request qdisc dump: {}", e);
+            return;
+        }
+    };
+
+    while let Ok(Some(message)) = response.try_next().await {
+        if let NetlinkPayload::InnerMessage(RtnlMessage::NewQueueDiscipline(tm)) = message.payload {
+            println!("Baseline qdisc: {}", describe_tc(&tm));
+        }
+    }
+}
+
+async fn handle_message(
+    queue: &EventQueue,
+    config: &Config,
+    ifaces: &mut IfaceCache,
+    msg: RtnlMessage,
+    dyndns: Option<&mut DynDnsUpdater>,
+) {
     match msg {
-        NewLink(lm) => on_link_created(dd, lm).await,
-        DelLink(lm) => on_link_deleted(dd, lm).await,
-        SetLink(lm) => on_link_set(dd, lm).await,
+        NewLink(lm) if config.forwards("link") => on_link_created(queue, lm, ifaces).await,
+        DelLink(lm) if config.forwards("link") => on_link_deleted(queue, lm, ifaces).await,
+        SetLink(lm) if config.forwards("link") => on_link_set(queue, lm, ifaces).await,
+        // Not forwarded, but the ifindex->name cache is still kept current.
+        NewLink(lm) | SetLink(lm) => {
+            let index = lm.header.index as u32;
+            if let Some(name) = find_ifname(lm) {
+                ifaces.insert(index, name);
+            }
+        }
+        DelLink(lm) => ifaces.remove(lm.header.index as u32),
         GetLink(_lm) => {}
-        NewAddress(_am) => {}
-        DelAddress(_am) => {}
+        NewAddress(am) => {
+            if let Some(updater) = dyndns {
+                updater.on_address_added(&am).await;
+            }
+            if config.forwards("address") {
+                on_address_created(queue, am, ifaces).await;
+            }
+        }
+        DelAddress(am) => {
+            if let Some(updater) = dyndns {
+                updater.on_address_removed(&am).await;
+            }
+            if config.forwards("address") {
+                on_address_deleted(queue, am, ifaces).await;
+            }
+        }
         GetAddress(_am) => {}
-        NewNeighbour(_nm) => {}
+        NewNeighbour(nm) if config.forwards("neighbour") => {
+            on_neighbour_created(queue, nm, ifaces).await
+        }
         GetNeighbour(_nm) => {}
-        DelNeighbour(_nm) => {}
+        DelNeighbour(nm) if config.forwards("neighbour") => {
+            on_neighbour_deleted(queue, nm, ifaces).await
+        }
+        NewNeighbour(_) | DelNeighbour(_) => {}
         NewRule(_rm) => {}
         DelRule(_rm) => {}
         GetRule(_rm) => {}
-        NewRoute(_rm) => {}
-        DelRoute(_rm) => {}
+        NewRoute(rm) if config.forwards("route") => on_route_created(queue, rm, ifaces).await,
+        DelRoute(rm) if config.forwards("route") => on_route_deleted(queue, rm, ifaces).await,
+        NewRoute(_) | DelRoute(_) => {}
         GetRoute(_rm) => {}
+        NewQueueDiscipline(tm) if config.forwards("qdisc") => on_qdisc_created(queue, tm).await,
+        DelQueueDiscipline(tm) if config.forwards("qdisc") => on_qdisc_deleted(queue, tm).await,
+        NewQueueDiscipline(_) | DelQueueDiscipline(_) => {}
+        GetQueueDiscipline(_tm) => {}
+        NewTrafficClass(tm) if config.forwards("class") => on_class_created(queue, tm).await,
+        DelTrafficClass(tm) if config.forwards("class") => on_class_deleted(queue, tm).await,
+        NewTrafficClass(_) | DelTrafficClass(_) => {}
+        GetTrafficClass(_tm) => {}
+        NewTrafficFilter(tm) if config.forwards("filter") => on_filter_created(queue, tm).await,
+        DelTrafficFilter(tm) if config.forwards("filter") => on_filter_deleted(queue, tm).await,
+        NewTrafficFilter(_) | DelTrafficFilter(_) => {}
+        GetTrafficFilter(_tm) => {}
         _ => {
             // NewNeighbourTable(NeighbourTableMessage),
             // GetNeighbourTable(NeighbourTableMessage),
             // SetNeighbourTable(NeighbourTableMessage),
-            // NewQueueDiscipline(TcMessage),
-            // DelQueueDiscipline(TcMessage),
-            // GetQueueDiscipline(TcMessage),
-            // NewTrafficClass(TcMessage),
-            // DelTrafficClass(TcMessage),
-            // GetTrafficClass(TcMessage),
-            // NewTrafficFilter(TcMessage),
-            // DelTrafficFilter(TcMessage),
-            // GetTrafficFilter(TcMessage),
             // NewTrafficChain(TcMessage),
             // DelTrafficChain(TcMessage),
             // GetTrafficChain(TcMessage),
@@ -0,0 +1,51 @@
+//! Listens for Ctrl-C and latches a shared flag so the main loop can stop
+//! reading netlink messages and the delivery sender can flush its queue and
+//! exit, instead of the process dying mid-send. Built on a `watch` channel
+//! rather than a bare `Notify`: a `Notify::notify_waiters()` call doesn't
+//! store a permit, so a Ctrl-C landing while a task is busy (e.g. blocked on
+//! `queue.push` backpressure or a dyndns HTTP call, not currently awaiting
+//! `notified()`) would otherwise be missed and never observed. `watch`
+//! tracks the current value, so `notified()` below sees a shutdown that
+//! already happened just as reliably as one that happens while it's waiting.
+
+use tokio::sync::watch;
+
+#[derive(Clone)]
+pub struct Shutdown {
+    rx: watch::Receiver<bool>,
+}
+
+impl Shutdown {
+    /// Installs a `ctrl_c` handler that latches this `Shutdown` once.
+    pub fn install() -> Self {
+        let (tx, rx) = watch::channel(false);
+
+        tokio::spawn(async move {
+            if let Err(e) = tokio::signal::ctrl_c().await {
+                eprintln!("Failed to install Ctrl-C handler: {}", e);
+                return;
+            }
+            println!("Shutdown requested, draining queued events...");
+            let _ = tx.send(true);
+        });
+
+        Shutdown { rx }
+    }
+
+    pub fn is_requested(&self) -> bool {
+        *self.rx.borrow()
+    }
+
+    /// Resolves once shutdown has been requested, whether that request
+    /// lands while this call is waiting or already happened beforehand.
+    pub async fn notified(&self) {
+        let mut rx = self.rx.clone();
+        while !*rx.borrow() {
+            if rx.changed().await.is_err() && !*rx.borrow() {
+                // The Ctrl-C handler could not be installed, so shutdown can
+                // never be requested.
+                futures::future::pending::<()>().await;
+            }
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+
+use zoomies::{Client, Event};
+
+use super::{EventSink, NetworkEvent};
+
+/// Forwards events to Datadog as agent events, the way navi always has.
+pub struct DatadogSink {
+    client: Client,
+}
+
+impl DatadogSink {
+    pub fn new(client: Client) -> Self {
+        DatadogSink { client }
+    }
+}
+
+#[async_trait]
+impl EventSink for DatadogSink {
+    async fn emit(&self, event: &NetworkEvent) -> Result<(), String> {
+        let built = Event::new()
+            .title(&event.title)
+            .text(&event.text)
+            .tags(event.tags.clone())
+            .build()
+            .map_err(|e| format!("{}", e))?;
+        self.client.send(&built).await.map_err(|e| format!("{}", e))
+    }
+}
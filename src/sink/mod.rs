@@ -0,0 +1,57 @@
+//! Backend-neutral event model and the `EventSink` abstraction. Handlers
+//! build a `NetworkEvent` once and hand it to every configured sink,
+//! instead of being wired directly to a single metrics vendor.
+
+mod datadog;
+mod mqtt;
+
+pub use datadog::DatadogSink;
+pub use mqtt::{MqttConfig, MqttSink};
+
+use async_trait::async_trait;
+
+/// A change navi observed on the host, described independently of any
+/// particular destination (Datadog, MQTT, ...).
+#[derive(Clone, Debug)]
+pub struct NetworkEvent {
+    pub title: String,
+    pub text: String,
+    pub tags: Vec<String>,
+    /// What kind of object changed, e.g. "link", "address", "qdisc". Used
+    /// by sinks that need structure beyond title/text, like MQTT topics.
+    pub category: &'static str,
+    /// What happened to it, e.g. "created", "deleted".
+    pub action: &'static str,
+    /// The object's identity, e.g. an interface name or an IP address.
+    pub subject: String,
+}
+
+impl NetworkEvent {
+    pub fn new(
+        title: impl Into<String>,
+        text: impl Into<String>,
+        category: &'static str,
+        action: &'static str,
+        subject: impl Into<String>,
+    ) -> Self {
+        NetworkEvent {
+            title: title.into(),
+            text: text.into(),
+            tags: Vec::new(),
+            category,
+            action,
+            subject: subject.into(),
+        }
+    }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+}
+
+/// A destination events can be dispatched to.
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    async fn emit(&self, event: &NetworkEvent) -> Result<(), String>;
+}
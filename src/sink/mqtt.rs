@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rumqttc::{AsyncClient, MqttOptions, QoS};
+
+use super::{EventSink, NetworkEvent};
+
+/// Connection details for the MQTT sink, sourced from the config file.
+#[derive(Clone)]
+pub struct MqttConfig {
+    pub broker_host: String,
+    pub broker_port: u16,
+    pub qos: QoS,
+}
+
+impl MqttConfig {
+    pub fn new(broker_host: String, broker_port: u16, qos: u8) -> Self {
+        let qos = match qos {
+            0 => QoS::AtMostOnce,
+            2 => QoS::ExactlyOnce,
+            _ => QoS::AtLeastOnce,
+        };
+        MqttConfig { broker_host, broker_port, qos }
+    }
+}
+
+/// Publishes events as JSON to an MQTT broker, so home-automation/IoT
+/// buses can react to the same changes navi reports to Datadog.
+pub struct MqttSink {
+    client: AsyncClient,
+    qos: QoS,
+    hostname: String,
+}
+
+impl MqttSink {
+    pub async fn connect(config: MqttConfig) -> Result<Self, String> {
+        let hostname = local_hostname();
+        let mut options =
+            MqttOptions::new(format!("navi-{}", hostname), config.broker_host, config.broker_port);
+        options.set_keep_alive(Duration::from_secs(30));
+
+        let (client, mut event_loop) = AsyncClient::new(options, 10);
+
+        // Drive the connection's event loop in the background; we only
+        // care that the socket stays alive, not about incoming packets.
+        tokio::spawn(async move {
+            loop {
+                // After an error, the next poll reconnects to the broker.
+                if let Err(e) = event_loop.poll().await {
+                    eprintln!("Mqtt: connection error: {}", e);
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+            }
+        });
+
+        Ok(MqttSink { client, qos: config.qos, hostname })
+    }
+
+    fn topic_for(&self, event: &NetworkEvent) -> String {
+        format!(
+            "navi/{}/{}/{}/{}",
+            self.hostname,
+            event.category,
+            sanitize_topic_level(&event.subject),
+            event.action
+        )
+    }
+}
+
+/// MQTT topics use `/` as a level separator, but some subjects embed one
+/// themselves (e.g. a route destination like `10.0.0.0/24`); replace it so
+/// the published topic keeps exactly the documented
+/// `navi/<hostname>/<category>/<subject>/<action>` shape.
+fn sanitize_topic_level(level: &str) -> String {
+    level.replace('/', "_")
+}
+
+#[async_trait]
+impl EventSink for MqttSink {
+    async fn emit(&self, event: &NetworkEvent) -> Result<(), String> {
+        let payload = serde_json::json!({
+            "title": event.title,
+            "text": event.text,
+            "tags": event.tags,
+        });
+        let topic = self.topic_for(event);
+        self.client
+            .publish(topic, self.qos, false, payload.to_string())
+            .await
+            .map_err(|e| format!("{}", e))
+    }
+}
+
+fn local_hostname() -> String {
+    hostname::get()
+        .ok()
+        .and_then(|h| h.into_string().ok())
+        .unwrap_or_else(|| "unknown-host".to_string())
+}